@@ -1,10 +1,25 @@
 use logos::{Lexer, Logos};
+use std::collections::VecDeque;
 use std::fmt::Debug;
+use std::ops::Range;
 
 pub trait TypeEq {
     fn type_eq(&self, other: &Self) -> bool;
 }
 
+/// compares two tokens by value rather than by variant, so grammars whose
+/// lexer lumps keywords into a single `Ident(String)` variant can still
+/// match a specific keyword like `for`/`if`
+pub trait ValueEq {
+    fn value_eq(&self, other: &Self) -> bool;
+}
+
+impl<T: PartialEq> ValueEq for T {
+    fn value_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
 pub struct Tokenizer<'a, Token>
 where
     Token: Logos<'a> + TypeEq + Clone + Debug,
@@ -20,6 +35,8 @@ where
     next_len: usize,
 
     ignore: Vec<Token>,
+    errors: Vec<Error>,
+    lookahead: VecDeque<(Token, usize, usize)>,
 }
 
 impl<'a, Token> Tokenizer<'a, Token>
@@ -94,11 +111,44 @@ where
             next_index,
             next_len,
             ignore,
+            errors: Vec::new(),
+            lookahead: VecDeque::new(),
         }
     }
 
-    pub fn peek(&self) -> Option<Token> {
-        self.next.clone()
+    /// pulls the next non-ignored token straight from the lexer, recording
+    /// its span; used to lazily fill `next` and the `peek_n` lookahead buffer
+    fn pull(&mut self) -> Option<(Token, usize, usize)> {
+        loop {
+            let token = self.lexer.next()?;
+            let index = self.lexer.span().start;
+            let len = self.lexer.span().len();
+            if !self.can_ignore(&token) {
+                return Some((token, index, len));
+            }
+        }
+    }
+
+    pub fn peek(&mut self) -> Option<Token> {
+        self.peek_n(0)
+    }
+
+    /// peeks `n` tokens ahead of `current()`, where `n == 0` is the same
+    /// token `peek()` returns; lazily pulls from the lexer and caches the
+    /// result so repeated peeks at the same depth are cheap
+    pub fn peek_n(&mut self, n: usize) -> Option<Token> {
+        if n == 0 {
+            return self.next.clone();
+        }
+
+        while self.lookahead.len() < n {
+            match self.pull() {
+                Some(entry) => self.lookahead.push_back(entry),
+                None => break,
+            }
+        }
+
+        self.lookahead.get(n - 1).map(|(token, _, _)| token.clone())
     }
 
     pub fn current(&self) -> Option<Token> {
@@ -109,6 +159,87 @@ where
         Error::new(Some(self.index), Some(self.len), msg.to_string())
     }
 
+    /// the byte range of `current()` in the source
+    pub fn current_span(&self) -> Range<usize> {
+        self.index..self.index + self.len
+    }
+
+    /// the byte range of `peek()` in the source
+    pub fn peek_span(&self) -> Range<usize> {
+        self.next_index..self.next_index + self.next_len
+    }
+
+    /// turns this `Tokenizer` into an iterator yielding each non-ignored
+    /// token paired with its byte range, for callers building an AST that
+    /// needs to attach source locations to nodes
+    pub fn spanned(self) -> Spanned<'a, Token> {
+        Spanned { tokenizer: self }
+    }
+
+    /// pushes `err` onto the internal error sink instead of returning it,
+    /// allowing a caller to keep parsing and collect multiple diagnostics
+    pub fn push_error(&mut self, err: Error) {
+        self.errors.push(err);
+    }
+
+    /// drains and returns all errors collected so far via `push_error`
+    pub fn take_errors(&mut self) -> Vec<Error> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// classic panic-mode recovery: advances `next()`, discarding tokens,
+    /// until `current()` type-matches one of `sync` or end of file is reached
+    pub fn synchronize(&mut self, sync: Vec<Token>) {
+        while let Some(token) = self.current() {
+            if sync.iter().any(|t| token.type_eq(t)) {
+                return;
+            }
+            self.next();
+        }
+    }
+
+    /// snapshots the tokenizer's position so it can later be restored with
+    /// `restore`, for recursive-descent parsers that try one production,
+    /// fail, and need to rewind to an earlier position
+    ///
+    /// checkpoints are only valid against the `Tokenizer` instance that
+    /// created them and should be restored in LIFO order for best performance
+    pub fn checkpoint(&mut self) -> Checkpoint<Token> {
+        Checkpoint {
+            current: self.current.clone(),
+            index: self.index,
+            len: self.len,
+            next: self.next.clone(),
+            next_index: self.next_index,
+            next_len: self.next_len,
+            lexer_pos: self.next_index + self.next_len,
+        }
+    }
+
+    /// rewinds the tokenizer to a previously taken `checkpoint`
+    ///
+    /// rebuilds the lexer from scratch and seeks it to `cp.lexer_pos`, which
+    /// resets any lexer `Extras` state back to its `Default` rather than the
+    /// value it held at checkpoint time
+    pub fn restore(&mut self, cp: Checkpoint<Token>)
+    where
+        <Token as Logos<'a>>::Extras: Default,
+    {
+        let mut lexer = Token::lexer(self.lexer.source());
+        lexer.bump(cp.lexer_pos);
+        self.lexer = lexer;
+
+        self.current = cp.current;
+        self.index = cp.index;
+        self.len = cp.len;
+
+        self.next = cp.next;
+        self.next_index = cp.next_index;
+        self.next_len = cp.next_len;
+
+        self.lookahead.clear();
+    }
+
     pub fn expect(&mut self, token: Token) -> Result<Token, Error> {
         if let Some(got) = self.current() {
             self.next();
@@ -188,38 +319,127 @@ where
     }
 }
 
+impl<'a, Token> Tokenizer<'a, Token>
+where
+    Token: Logos<'a, Source = str> + TypeEq + Clone + Debug,
+{
+    /// renders `err` as a rustc-style diagnostic: the source line it points
+    /// at, followed by a caret line underlining the `index..index+len` span
+    pub fn render_error(&self, err: &Error) -> String {
+        let source = self.lexer.source();
+        let index = match err.index {
+            Some(index) => index,
+            None => return err.to_string(),
+        };
+        let len = err.len.unwrap_or(0);
+
+        let line = source[..index].matches('\n').count() + 1;
+        let line_start = source[..index].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = index - line_start;
+
+        let line_end = source[index..]
+            .find('\n')
+            .map(|i| index + i)
+            .unwrap_or(source.len());
+        let line_text = &source[line_start..line_end];
+
+        let caret = format!("{}{}", " ".repeat(column), "^".repeat(len.max(1)));
+        format!("{}\nline {}:\n{}\n{}", err, line, line_text, caret)
+    }
+}
+
+impl<'a, Token> Tokenizer<'a, Token>
+where
+    Token: Logos<'a> + TypeEq + Clone + Debug + ValueEq,
+{
+    pub fn expect_value(&mut self, token: Token) -> Result<Token, Error> {
+        if let Some(got) = self.current() {
+            self.next();
+            if token.value_eq(&got) {
+                Ok(got)
+            } else {
+                Err(self.error(&format!("expect token {:?} but got {:?}", token, got)))
+            }
+        } else {
+            Err(self.error(&format!(
+                "expect token {:?} but got {}",
+                token, "end of file"
+            )))
+        }
+    }
+
+    pub fn is_value(&self, token: Token) -> bool {
+        if let Some(got) = self.current.clone() {
+            return got.value_eq(&token);
+        }
+        return false;
+    }
+
+    pub fn next_is_value(&self, token: Token) -> bool {
+        if let Some(got) = self.next.clone() {
+            return got.value_eq(&token);
+        }
+        return false;
+    }
+}
+
 impl<'a, Token> Iterator for Tokenizer<'a, Token>
 where
     Token: Logos<'a> + TypeEq + Clone + Debug,
 {
     type Item = Token;
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            self.current = self.next.clone();
-            self.index = self.next_index;
-            self.len = self.next_len;
-
-            self.next = self.lexer.next();
-            self.next_index = self.lexer.span().start;
-            self.next_len = self.lexer.span().len();
-
-            if let Some(token) = self.current.clone() {
-                if !self.can_ignore(&token) {
-                    while let Some(token) = self.next.clone() {
-                        if self.can_ignore(&token) {
-                            self.next = self.lexer.next();
-                            self.next_index = self.lexer.span().start;
-                            self.next_len = self.lexer.span().len();
-                        } else {
-                            break;
-                        }
-                    }
-                    return self.current.clone();
-                }
-            } else {
-                return self.current.clone();
+        self.current = self.next.take();
+        self.index = self.next_index;
+        self.len = self.next_len;
+
+        match self.lookahead.pop_front().or_else(|| self.pull()) {
+            Some((token, index, len)) => {
+                self.next = Some(token);
+                self.next_index = index;
+                self.next_len = len;
+            }
+            None => {
+                self.next = None;
             }
         }
+
+        self.current.clone()
+    }
+}
+
+/// an opaque snapshot of a [`Tokenizer`]'s position, produced by
+/// `Tokenizer::checkpoint` and consumed by `Tokenizer::restore`
+#[derive(Debug, Clone)]
+pub struct Checkpoint<Token> {
+    current: Option<Token>,
+    index: usize,
+    len: usize,
+
+    next: Option<Token>,
+    next_index: usize,
+    next_len: usize,
+
+    lexer_pos: usize,
+}
+
+/// iterator adapter returned by [`Tokenizer::spanned`] that yields each
+/// non-ignored token together with its byte range in the source
+pub struct Spanned<'a, Token>
+where
+    Token: Logos<'a> + TypeEq + Clone + Debug,
+{
+    tokenizer: Tokenizer<'a, Token>,
+}
+
+impl<'a, Token> Iterator for Spanned<'a, Token>
+where
+    Token: Logos<'a> + TypeEq + Clone + Debug,
+{
+    type Item = (Token, Range<usize>);
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.tokenizer.next()?;
+        Some((token, self.tokenizer.current_span()))
     }
 }
 
@@ -235,3 +455,12 @@ impl Error {
         Self { index, len, msg }
     }
 }
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.index, self.len) {
+            (Some(index), Some(len)) => write!(f, "{} at {}..{}", self.msg, index, index + len),
+            _ => write!(f, "{}", self.msg),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use logos::Logos;
+use tokenizer::{Tokenizer, TypeEq};
+
+#[test]
+fn checkpoint_restores_correctly_even_after_peeking_ahead() {
+    let code = "a b c d";
+    let mut tokenizer = Tokenizer::new(Token::lexer(code), vec![Token::Space]);
+
+    tokenizer.next(); // current = a, next = b
+
+    // peek past `next` before taking the checkpoint, so the lexer's raw
+    // cursor is ahead of the position the checkpoint should restore to
+    assert_eq!(tokenizer.peek_n(2), Some(Token::Ident("d".to_string())));
+    let cp = tokenizer.checkpoint();
+
+    tokenizer.next(); // current = b
+    tokenizer.next(); // current = c
+    tokenizer.next(); // current = d
+
+    tokenizer.restore(cp);
+
+    assert_eq!(tokenizer.current(), Some(Token::Ident("a".to_string())));
+    assert_eq!(tokenizer.peek(), Some(Token::Ident("b".to_string())));
+    assert_eq!(tokenizer.next(), Some(Token::Ident("b".to_string())));
+    assert_eq!(tokenizer.next(), Some(Token::Ident("c".to_string())));
+    assert_eq!(tokenizer.next(), Some(Token::Ident("d".to_string())));
+    assert_eq!(tokenizer.next(), None);
+}
+
+#[derive(Logos, Debug, Clone, PartialEq)]
+enum Token {
+    #[regex(r"[a-z]+", |lex| lex.slice().parse())]
+    Ident(String),
+    #[token(" ")]
+    Space,
+    #[error]
+    Unknown,
+}
+
+impl TypeEq for Token {
+    fn type_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Token::Ident(_), Token::Ident(_)) => true,
+            (Token::Space, Token::Space) => true,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use logos::Logos;
+use tokenizer::{Tokenizer, TypeEq};
+
+#[test]
+fn peek_n_looks_arbitrarily_far_ahead_and_caches() {
+    let code = "a b c d";
+    let mut tokenizer = Tokenizer::new(Token::lexer(code), vec![Token::Space]);
+
+    assert_eq!(tokenizer.peek_n(0), Some(Token::Ident("a".to_string())));
+    assert_eq!(tokenizer.peek_n(1), Some(Token::Ident("b".to_string())));
+    assert_eq!(tokenizer.peek_n(2), Some(Token::Ident("c".to_string())));
+    // repeated peeks at the same depth return the cached token
+    assert_eq!(tokenizer.peek_n(2), Some(Token::Ident("c".to_string())));
+
+    assert_eq!(tokenizer.next(), Some(Token::Ident("a".to_string())));
+    assert_eq!(tokenizer.peek_n(0), Some(Token::Ident("b".to_string())));
+    assert_eq!(tokenizer.peek_n(1), Some(Token::Ident("c".to_string())));
+    assert_eq!(tokenizer.peek_n(2), Some(Token::Ident("d".to_string())));
+    assert_eq!(tokenizer.peek_n(3), None);
+}
+
+#[derive(Logos, Debug, Clone, PartialEq)]
+enum Token {
+    #[regex(r"[a-z]+", |lex| lex.slice().parse())]
+    Ident(String),
+    #[token(" ")]
+    Space,
+    #[error]
+    Unknown,
+}
+
+impl TypeEq for Token {
+    fn type_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Token::Ident(_), Token::Ident(_)) => true,
+            (Token::Space, Token::Space) => true,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use logos::Logos;
+use tokenizer::{Error, Tokenizer, TypeEq};
+
+#[test]
+fn render_error_prints_line_and_caret_under_span() {
+    let code = "let x\nlet 1bad";
+    let tokenizer = Tokenizer::new(Token::lexer(code), vec![Token::Space]);
+
+    let err = Error::new(Some(10), Some(3), "unexpected token".to_string());
+    let rendered = tokenizer.render_error(&err);
+
+    assert!(rendered.contains("unexpected token"));
+    assert!(rendered.contains("line 2:"));
+    assert!(rendered.contains("let 1bad"));
+    assert!(rendered.contains("    ^^^"));
+}
+
+#[derive(Logos, Debug, Clone, PartialEq)]
+enum Token {
+    #[regex(r"[a-zA-Z0-9]+", |lex| lex.slice().parse())]
+    Ident(String),
+    #[token(" ")]
+    Space,
+    #[token("\n")]
+    Newline,
+    #[error]
+    Unknown,
+}
+
+impl TypeEq for Token {
+    fn type_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Token::Ident(_), Token::Ident(_)) => true,
+            (Token::Space, Token::Space) => true,
+            (Token::Newline, Token::Newline) => true,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use logos::Logos;
+use std::ops::Range;
+use tokenizer::{Tokenizer, TypeEq};
+
+#[test]
+fn spanned_yields_byte_ranges_for_each_token() {
+    let code = "aa  bbb";
+    let tokenizer = Tokenizer::new(Token::lexer(code), vec![Token::Space]);
+    let spans: Vec<(Token, Range<usize>)> = tokenizer.spanned().collect();
+
+    assert_eq!(
+        spans,
+        vec![
+            (Token::Ident("aa".to_string()), 0..2),
+            (Token::Ident("bbb".to_string()), 4..7),
+        ]
+    );
+}
+
+#[test]
+fn current_and_peek_span_track_the_active_tokens() {
+    let code = "aa bbb";
+    let mut tokenizer = Tokenizer::new(Token::lexer(code), vec![Token::Space]);
+
+    tokenizer.next();
+    assert_eq!(tokenizer.current_span(), 0..2);
+    assert_eq!(tokenizer.peek_span(), 3..6);
+}
+
+#[derive(Logos, Debug, Clone, PartialEq)]
+enum Token {
+    #[regex(r"[a-z]+", |lex| lex.slice().parse())]
+    Ident(String),
+    #[token(" ")]
+    Space,
+    #[error]
+    Unknown,
+}
+
+impl TypeEq for Token {
+    fn type_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Token::Ident(_), Token::Ident(_)) => true,
+            (Token::Space, Token::Space) => true,
+            _ => false,
+        }
+    }
+}
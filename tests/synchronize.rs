@@ -0,0 +1,40 @@
+use logos::Logos;
+use tokenizer::{Tokenizer, TypeEq};
+
+#[test]
+fn synchronizes_to_next_statement_boundary_after_expect_failure() {
+    let code = "a a ; a a ; a";
+    let mut tokenizer = Tokenizer::new(Token::lexer(code), vec![Token::Space]);
+    tokenizer.next();
+
+    let err = tokenizer.expect(Token::Semi).unwrap_err();
+    tokenizer.push_error(err);
+    tokenizer.synchronize(vec![Token::Semi]);
+
+    assert_eq!(tokenizer.current(), Some(Token::Semi));
+    assert_eq!(tokenizer.take_errors().len(), 1);
+    assert!(tokenizer.take_errors().is_empty());
+}
+
+#[derive(Logos, Debug, Clone, PartialEq)]
+enum Token {
+    #[token("a")]
+    A,
+    #[token(";")]
+    Semi,
+    #[token(" ")]
+    Space,
+    #[error]
+    Unknown,
+}
+
+impl TypeEq for Token {
+    fn type_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Token::A, Token::A) => true,
+            (Token::Semi, Token::Semi) => true,
+            (Token::Space, Token::Space) => true,
+            _ => false,
+        }
+    }
+}
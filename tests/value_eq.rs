@@ -0,0 +1,39 @@
+use logos::Logos;
+use tokenizer::{Tokenizer, TypeEq};
+
+#[test]
+fn value_eq_methods_match_exact_keyword_payloads() {
+    let code = "if foo";
+    let mut tokenizer = Tokenizer::new(Token::lexer(code), vec![Token::Space]);
+
+    tokenizer.next();
+    assert!(tokenizer.is_value(Token::Ident("if".to_string())));
+    assert!(!tokenizer.is_value(Token::Ident("for".to_string())));
+    assert!(tokenizer.next_is_value(Token::Ident("foo".to_string())));
+    assert!(!tokenizer.next_is_value(Token::Ident("bar".to_string())));
+
+    assert_eq!(
+        tokenizer.expect_value(Token::Ident("if".to_string())),
+        Ok(Token::Ident("if".to_string()))
+    );
+}
+
+#[derive(Logos, Debug, Clone, PartialEq)]
+enum Token {
+    #[regex(r"[a-z]+", |lex| lex.slice().parse())]
+    Ident(String),
+    #[token(" ")]
+    Space,
+    #[error]
+    Unknown,
+}
+
+impl TypeEq for Token {
+    fn type_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Token::Ident(_), Token::Ident(_)) => true,
+            (Token::Space, Token::Space) => true,
+            _ => false,
+        }
+    }
+}